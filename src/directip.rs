@@ -0,0 +1,228 @@
+//! DirectIP is one of the two ways Iridium SBD messages travel: over TCP,
+//! directly from (mobile-originated) or to (mobile-terminated) an Iridium
+//! gateway.
+//!
+//! This module provides the client used to submit mobile-terminated messages
+//! to a gateway. Receiving mobile-originated traffic over DirectIP is not yet
+//! implemented by this crate.
+
+use std::io::Write;
+use std::net::TcpStream;
+use std::time::Duration;
+
+use byteorder::{BigEndian, WriteBytesExt};
+
+use crate::mt::{self, ConfirmationStatus, DispositionFlags};
+use crate::{Result, SbdError};
+
+/// The default number of times an `MtClient` will retry a failed submission.
+const DEFAULT_RETRIES: usize = 3;
+
+/// The default time to wait for the gateway's confirmation message.
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Builds a Mobile Terminated message to be submitted through an `MtClient`.
+#[derive(Debug)]
+pub struct MtMessageBuilder {
+    client_msg_id: u32,
+    imei: [u8; 15],
+    flags: DispositionFlags,
+    payload: Vec<u8>,
+}
+
+impl MtMessageBuilder {
+    /// Starts building an MT message for the given IMEI and client message id.
+    ///
+    /// The client message id is chosen by the caller and is echoed back in the
+    /// gateway's confirmation, so it can be used to match requests to responses.
+    pub fn new(imei: &str, client_msg_id: u32) -> Result<MtMessageBuilder> {
+        Ok(MtMessageBuilder {
+            client_msg_id,
+            imei: mt::imei_from_str(imei)?,
+            flags: DispositionFlags::default(),
+            payload: Vec::new(),
+        })
+    }
+
+    /// Sets the message's payload.
+    pub fn payload(mut self, payload: Vec<u8>) -> MtMessageBuilder {
+        self.payload = payload;
+        self
+    }
+
+    /// Asks the gateway to flush the MT queue for this IMEI before delivering this message.
+    pub fn flush_queue(mut self, flush_queue: bool) -> MtMessageBuilder {
+        self.flags.flush_queue = flush_queue;
+        self
+    }
+
+    /// Asks the gateway to send a ring alert to wake up the modem.
+    pub fn send_ring_alert(mut self, send_ring_alert: bool) -> MtMessageBuilder {
+        self.flags.send_ring_alert = send_ring_alert;
+        self
+    }
+
+    /// Asks the gateway to request an updated location from the modem.
+    pub fn update_location(mut self, update_location: bool) -> MtMessageBuilder {
+        self.flags.update_location = update_location;
+        self
+    }
+
+    /// Marks this message as high priority.
+    pub fn high_priority(mut self, high_priority: bool) -> MtMessageBuilder {
+        self.flags.high_priority = high_priority;
+        self
+    }
+
+    /// Asks the gateway to assign an MTMSN to this message.
+    pub fn assign_mtmsn(mut self, assign_mtmsn: bool) -> MtMessageBuilder {
+        self.flags.assign_mtmsn = assign_mtmsn;
+        self
+    }
+
+    fn header(&self) -> mt::Header {
+        mt::Header::new(self.client_msg_id, self.imei, self.flags)
+    }
+}
+
+/// The gateway's response to a successful MT submission.
+#[derive(Debug, Clone, Copy)]
+pub struct MtConfirmation {
+    /// The Auto ID Reference assigned to this message by the gateway.
+    pub auto_id_reference: u32,
+    /// This message's position in the MT queue, or `None` if it was sent with no queue.
+    pub queue_position: Option<u16>,
+}
+
+/// A client for submitting Mobile Terminated messages to an Iridium DirectIP gateway.
+#[derive(Debug)]
+pub struct MtClient {
+    addr: String,
+    read_timeout: Option<Duration>,
+    retries: usize,
+}
+
+impl MtClient {
+    /// Creates a client targeting the given gateway host and port.
+    pub fn new(host: &str, port: u16) -> MtClient {
+        MtClient {
+            addr: format!("{}:{}", host, port),
+            read_timeout: Some(DEFAULT_READ_TIMEOUT),
+            retries: DEFAULT_RETRIES,
+        }
+    }
+
+    /// Sets how long to wait for the gateway's confirmation before giving up.
+    pub fn read_timeout(mut self, timeout: Duration) -> MtClient {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how many times to retry a submission that fails before a confirmation is read.
+    pub fn retries(mut self, retries: usize) -> MtClient {
+        self.retries = retries;
+        self
+    }
+
+    /// Submits the message built by `builder`, returning the gateway's confirmation.
+    ///
+    /// If the builder's payload exceeds the wire format's 1890-byte maximum,
+    /// this returns `SbdError::OversizedMtPayload` without attempting a
+    /// connection. If an attempt fails before a confirmation is read (while
+    /// connecting, sending, or reading and parsing the gateway's response), the
+    /// whole submission is retried up to `self.retries` times before the error
+    /// is returned to the caller.
+    /// If the gateway reports a negative status, that's surfaced as
+    /// `SbdError::MtDeliveryFailed` rather than as a successful confirmation.
+    pub fn send(&self, builder: MtMessageBuilder) -> Result<MtConfirmation> {
+        if builder.payload.len() > mt::Payload::MAX_LEN {
+            return Err(SbdError::OversizedMtPayload(builder.payload.len()));
+        }
+        let mut attempt = 0;
+        loop {
+            match self.send_once(&builder) {
+                Ok(confirmation) => return check_confirmation(confirmation),
+                Err(err) => {
+                    attempt += 1;
+                    if attempt > self.retries {
+                        return Err(err);
+                    }
+                    warn!(
+                        "DirectIP submission attempt {} to {} failed: {}, retrying",
+                        attempt, self.addr, err
+                    );
+                }
+            }
+        }
+    }
+
+    fn send_once(&self, builder: &MtMessageBuilder) -> Result<mt::Confirmation> {
+        let header = builder.header();
+        let mut body = Vec::new();
+        header.write(&mut body)?;
+        if !builder.payload.is_empty() {
+            mt::Payload::new(builder.payload.clone()).write(&mut body)?;
+        }
+
+        let mut stream = TcpStream::connect(&self.addr)?;
+        stream.set_read_timeout(self.read_timeout)?;
+        stream.write_u8(1)?; // Protocol revision number.
+        stream.write_u16::<BigEndian>(
+            body.len()
+                .try_into()
+                .expect("MT message was too large to submit"),
+        )?;
+        stream.write_all(&body)?;
+
+        let response = mt::Message::read_from(&mut stream)?;
+        response
+            .confirmation
+            .ok_or(SbdError::MissingMtConfirmationMessage)
+    }
+}
+
+fn check_confirmation(confirmation: mt::Confirmation) -> Result<MtConfirmation> {
+    match confirmation.status() {
+        ConfirmationStatus::Error(_) => Err(SbdError::MtDeliveryFailed(confirmation.raw_status())),
+        ConfirmationStatus::Queued(position) => Ok(MtConfirmation {
+            auto_id_reference: confirmation.auto_id_reference(),
+            queue_position: Some(position),
+        }),
+        ConfirmationStatus::NoQueue => Ok(MtConfirmation {
+            auto_id_reference: confirmation.auto_id_reference(),
+            queue_position: None,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test_mt_message_builder {
+    use super::{MtClient, MtMessageBuilder};
+
+    #[test]
+    fn new_rejects_bad_imei() {
+        assert!(MtMessageBuilder::new("too-short", 1).is_err());
+    }
+
+    #[test]
+    fn send_rejects_oversized_payload() {
+        let builder = MtMessageBuilder::new("300034010123450", 9999)
+            .unwrap()
+            .payload(vec![0u8; 1891]);
+        let client = MtClient::new("127.0.0.1", 0);
+        assert!(client.send(builder).is_err());
+    }
+
+    #[test]
+    fn header_encodes_disposition_flags() {
+        let builder = MtMessageBuilder::new("300034010123450", 9999)
+            .unwrap()
+            .flush_queue(true)
+            .assign_mtmsn(true);
+        let mut msg = vec![];
+        builder.header().write(&mut msg).unwrap();
+        // Disposition flags are the trailing 2 bytes of the header element.
+        let flags = u16::from(msg[msg.len() - 2]) << 8 | u16::from(msg[msg.len() - 1]);
+        assert_eq!(flags, 0b10_0001);
+    }
+}
@@ -0,0 +1,25 @@
+//! A common interface for Mobile Originated and Mobile Terminated headers.
+//!
+//! Both directions frame their header as an information element with a fixed
+//! body length and carry the same IMEI field, just at different IEIs and with
+//! different trailing fields. This trait lets `message::Message` treat either
+//! header uniformly once it has sniffed which direction it is decoding.
+
+use std::io::{Read, Write};
+
+use crate::Result;
+
+/// Behavior shared by the MO and MT headers.
+pub(crate) trait SbdHeader: Sized {
+    /// Writes this header as an information element, including its IEI.
+    fn write_to<W: Write>(&self, wtr: &mut W) -> Result<usize>;
+
+    /// Reads a header from a stream, assuming the IEI byte has already been consumed.
+    fn read_from<R: Read>(rdr: &mut R) -> Result<Self>;
+
+    /// The IMEI this header was addressed to or received from.
+    fn imei(&self) -> &str;
+
+    /// The length of this header's body, not including the IEI and length fields.
+    fn len(&self) -> usize;
+}
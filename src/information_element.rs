@@ -0,0 +1,218 @@
+//! Information elements shared by mobile-originated and mobile-terminated messages.
+//!
+//! Some information elements (e.g. the GPS location the Iridium network attaches
+//! to a message) use the same wire format in both directions, differing only in
+//! their IEI. Those elements live here so the `mo` and `mt` modules can share one
+//! implementation.
+//!
+//! [`InformationElement`] itself is generic over a direction's header and
+//! payload types, so the `H`/`P`/`L`/`Unknown` dispatch and the forward-compatible
+//! handling of unrecognized IEIs are written once and shared by both directions.
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::header::SbdHeader;
+use crate::{Result, SbdError};
+
+/// Behavior shared by the MO and MT payloads, analogous to `SbdHeader`.
+pub(crate) trait SbdPayload: Sized {
+    /// Writes this payload as an information element, including its IEI.
+    fn write_to<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize>;
+
+    /// Reads a payload from a stream, assuming the IEI byte has already been consumed.
+    fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Self>;
+
+    /// The length of this payload's body, not including the IEI and length fields.
+    fn len(&self) -> usize;
+}
+
+/// An information element common to both directions: a typed header, payload,
+/// or GPS location, or an unrecognized element preserved verbatim.
+///
+/// Generic over the direction's header type `H` and payload type `P` so that
+/// `mo` and `mt` can share this dispatch instead of each maintaining their own
+/// copy.
+#[derive(Debug)]
+pub(crate) enum InformationElement<H, P> {
+    H(H),
+    P(P),
+    L(Location),
+    /// An information element this crate does not yet know how to decode.
+    ///
+    /// The IEI and raw body are preserved verbatim so that messages carrying
+    /// future or vendor-specific elements can still round-trip through a
+    /// read/write cycle.
+    Unknown { iei: u8, bytes: Vec<u8> },
+}
+
+impl<H: SbdHeader, P: SbdPayload> InformationElement<H, P> {
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            InformationElement::H(element) => element.len(),
+            InformationElement::P(element) => element.len(),
+            InformationElement::L(element) => element.len(),
+            InformationElement::Unknown { bytes, .. } => bytes.len(),
+        }
+    }
+
+    /// Writes this element. `location_iei` is used if this is a `Location`, the
+    /// only variant whose IEI differs between directions.
+    pub(crate) fn write<W: std::io::Write>(&self, wtr: &mut W, location_iei: u8) -> Result<usize> {
+        match self {
+            InformationElement::H(element) => element.write_to(wtr),
+            InformationElement::P(element) => element.write_to(wtr),
+            InformationElement::L(element) => element.write(wtr, location_iei),
+            InformationElement::Unknown { iei, bytes } => {
+                wtr.write_u8(*iei)?;
+                let n = bytes.len();
+                wtr.write_u16::<BigEndian>(
+                    n.try_into()
+                        .expect("Information element's length was supposed to be u16"),
+                )?;
+                wtr.write_all(bytes)?;
+                Ok(3 + n)
+            }
+        }
+    }
+
+    /// Reads an information element's body, given that its IEI byte `iei` has
+    /// already been read from `rdr`.
+    ///
+    /// Unrecognized IEIs are not an error: their body is read generically and
+    /// preserved as `InformationElement::Unknown`.
+    pub(crate) fn read_with_iei<R: std::io::Read>(
+        iei: u8,
+        rdr: &mut R,
+        header_iei: u8,
+        payload_iei: u8,
+        location_iei: u8,
+    ) -> Result<InformationElement<H, P>> {
+        match iei {
+            _ if iei == header_iei => Ok(InformationElement::H(H::read_from(rdr)?)),
+            _ if iei == payload_iei => Ok(InformationElement::P(P::read_from(rdr)?)),
+            _ if iei == location_iei => Ok(InformationElement::L(Location::read_from(rdr)?)),
+            _ => {
+                let length = rdr.read_u16::<BigEndian>()?;
+                let mut bytes = vec![0u8; length as usize];
+                rdr.read_exact(&mut bytes)?;
+                Ok(InformationElement::Unknown { iei, bytes })
+            }
+        }
+    }
+}
+
+/// Lat/Lon Location Information
+///
+/// IEI `0x03` for mobile-originated messages, `0x43` for mobile-terminated
+/// messages. The body is always 11 bytes: a flags byte, a degrees/thousandths-of-minutes
+/// latitude, a degrees/thousandths-of-minutes longitude, and a CEP radius in km.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Location {
+    pub(crate) latitude: f64,
+    pub(crate) longitude: f64,
+    pub(crate) cep_radius_km: u32,
+}
+
+impl Location {
+    pub(crate) fn len(&self) -> usize {
+        11
+    }
+
+    /// Writes this location as an information element with the given IEI.
+    pub(crate) fn write<W: std::io::Write>(&self, wtr: &mut W, iei: u8) -> Result<usize> {
+        wtr.write_u8(iei)?;
+        wtr.write_u16::<BigEndian>(11)?;
+        let mut flags = 0u8;
+        if self.longitude < 0.0 {
+            flags |= 0b10;
+        }
+        if self.latitude < 0.0 {
+            flags |= 0b01;
+        }
+        wtr.write_u8(flags)?;
+        let (lat_degrees, lat_thousandths) = to_degrees_and_thousandths(self.latitude);
+        wtr.write_u8(lat_degrees)?;
+        wtr.write_u16::<BigEndian>(lat_thousandths)?;
+        let (lon_degrees, lon_thousandths) = to_degrees_and_thousandths(self.longitude);
+        wtr.write_u8(lon_degrees)?;
+        wtr.write_u16::<BigEndian>(lon_thousandths)?;
+        wtr.write_u32::<BigEndian>(self.cep_radius_km)?;
+        Ok(14)
+    }
+
+    /// Reads a `Location` from a stream, assuming the IEI byte has already been consumed.
+    pub(crate) fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Location> {
+        let length = rdr.read_u16::<BigEndian>()?;
+        if length != 11 {
+            return Err(SbdError::InvalidLocationLength(length));
+        }
+        let flags = rdr.read_u8()?;
+        let south = flags & 0b01 != 0;
+        let west = flags & 0b10 != 0;
+        let lat_degrees = rdr.read_u8()?;
+        let lat_thousandths = rdr.read_u16::<BigEndian>()?;
+        let lon_degrees = rdr.read_u8()?;
+        let lon_thousandths = rdr.read_u16::<BigEndian>()?;
+        let cep_radius_km = rdr.read_u32::<BigEndian>()?;
+        let mut latitude = from_degrees_and_thousandths(lat_degrees, lat_thousandths);
+        if south {
+            latitude = -latitude;
+        }
+        let mut longitude = from_degrees_and_thousandths(lon_degrees, lon_thousandths);
+        if west {
+            longitude = -longitude;
+        }
+        Ok(Location {
+            latitude,
+            longitude,
+            cep_radius_km,
+        })
+    }
+}
+
+fn to_degrees_and_thousandths(decimal_degrees: f64) -> (u8, u16) {
+    let decimal_degrees = decimal_degrees.abs();
+    let degrees = decimal_degrees.trunc() as u8;
+    let thousandths_of_minutes = (decimal_degrees.fract() * 60.0 * 1000.0).round() as u16;
+    (degrees, thousandths_of_minutes)
+}
+
+fn from_degrees_and_thousandths(degrees: u8, thousandths_of_minutes: u16) -> f64 {
+    f64::from(degrees) + (f64::from(thousandths_of_minutes) / 1000.0) / 60.0
+}
+
+#[cfg(test)]
+mod test_location {
+    use super::Location;
+
+    #[test]
+    fn write_then_read_from_northeast() {
+        let location = Location {
+            latitude: 27.5,
+            longitude: 112.25,
+            cep_radius_km: 4,
+        };
+        let mut msg = vec![];
+        let n = location.write(&mut msg, 0x43).unwrap();
+        assert_eq!(n, 14);
+        // Skip the IEI byte, as the dispatcher in `InformationElement::read_with_iei` would.
+        let read_location = Location::read_from(&mut &msg[1..]).unwrap();
+        assert!((read_location.latitude - 27.5).abs() < 1e-3);
+        assert!((read_location.longitude - 112.25).abs() < 1e-3);
+        assert_eq!(read_location.cep_radius_km, 4);
+    }
+
+    #[test]
+    fn write_then_read_from_southwest() {
+        let location = Location {
+            latitude: -27.5,
+            longitude: -112.25,
+            cep_radius_km: 4,
+        };
+        let mut msg = vec![];
+        location.write(&mut msg, 0x03).unwrap();
+        let read_location = Location::read_from(&mut &msg[1..]).unwrap();
+        assert!((read_location.latitude + 27.5).abs() < 1e-3);
+        assert!((read_location.longitude + 112.25).abs() < 1e-3);
+    }
+}
@@ -11,9 +11,9 @@
 //! messages are delivered either to an email address via MIME attachment, or directly to a given
 //! IP address and port via TCP; this second method is called DirectIP.
 //!
-//! This is a simple library for reading mobile originated SBD messages from a stream, decoding
-//! their headers and data payloads, and writing them back to a stream. This library does not
-//! handle mobile terminated messages.
+//! This is a simple library for reading and writing SBD messages from a stream, decoding
+//! their headers and data payloads. The [`message::Message`] type handles both directions,
+//! sniffing which one a stream carries as it decodes.
 //!
 //! Most of the functionality of this library is exposed by a single executable, named `sbd` that
 //! is part of this package. Use the `sbd` executable to inspect raw sbd files stores on a
@@ -25,10 +25,13 @@
         unused_import_braces, unused_qualifications)]
 
 pub mod directip;
+mod header;
 mod information_element;
 pub mod logger;
 pub mod filesystem;
 pub mod message;
+mod mo;
+mod mt;
 
 pub use message::Message;
 
@@ -53,16 +56,42 @@ pub enum SbdError {
     InvalidImei,
     /// Invalid protocol revision number.
     InvalidProtocolRevisionNumber(u8),
+    /// Invalid mobile terminated header length.
+    InvalidMtHeaderLength(u16),
+    /// Invalid mobile terminated confirmation message length.
+    InvalidMtConfirmationLength(u16),
+    /// Invalid lat/lon location information length.
+    InvalidLocationLength(u16),
+    /// Invalid mobile originated header length.
+    InvalidMoHeaderLength(u16),
+    /// A message's declared overall length did not match the total size of
+    /// the information elements actually present.
+    InvalidMessageLength(usize),
+    /// The leading information element's IEI did not identify either an MO
+    /// header (`0x01`) or an MT header (`0x41`).
+    UnknownMessageDirection(u8),
+    /// The Iridium gateway rejected a submitted mobile terminated message.
+    ///
+    /// Carries the raw, negative MT message status reported by the gateway.
+    MtDeliveryFailed(i16),
+    /// The gateway's response to an MT submission did not include a confirmation message.
+    MissingMtConfirmationMessage,
     /// Wrapper around a glob error.
     Glob(glob::GlobError),
     /// Missing mobile originated header.
     MissingMobileOriginatedHeader,
     /// Missing mobile originated payload.
     MissingMobileOriginatedPayload,
+    /// Missing mobile terminated header.
+    MissingMobileTerminatedHeader,
+    /// Missing mobile terminated payload.
+    MissingMobileTerminatedPayload,
     /// An oversized message.
     ///
     /// Oversized doesn't demand a size since we don't want to find out how much there really is.
     Oversized,
+    /// An MT payload larger than the DirectIP wire format's 1890-byte maximum.
+    OversizedMtPayload(usize),
     /// Wrapper around a glob::PatternError.
     Pattern(glob::PatternError),
     /// An undersized message.
@@ -76,10 +105,21 @@ impl fmt::Display for SbdError {
             SbdError::Io(ref err) => write!(f, "IO error: {}", err),
             SbdError::InvalidImei => write!(f, "Invalid IMEI number"),
             SbdError::InvalidProtocolRevisionNumber(number) => write!(f, "Invalid protocl revision number: {}", number),
+            SbdError::InvalidMtHeaderLength(length) => write!(f, "Invalid mobile terminated header length: {}", length),
+            SbdError::InvalidMtConfirmationLength(length) => write!(f, "Invalid mobile terminated confirmation message length: {}", length),
+            SbdError::InvalidLocationLength(length) => write!(f, "Invalid lat/lon location information length: {}", length),
+            SbdError::InvalidMoHeaderLength(length) => write!(f, "Invalid mobile originated header length: {}", length),
+            SbdError::InvalidMessageLength(length) => write!(f, "Declared message length {} did not match the information elements present", length),
+            SbdError::UnknownMessageDirection(iei) => write!(f, "Leading information element IEI {:#x} is neither an MO nor an MT header", iei),
+            SbdError::MtDeliveryFailed(status) => write!(f, "Gateway rejected MT message, status: {}", status),
+            SbdError::MissingMtConfirmationMessage => write!(f, "Gateway response did not include a confirmation message"),
             SbdError::Glob(ref err) => write!(f, "Glob error: {}", err),
             SbdError::MissingMobileOriginatedHeader => write!(f, "Missing mobile origianted header"),
             SbdError::MissingMobileOriginatedPayload => write!(f, "Missing mobile orignated payload"),
+            SbdError::MissingMobileTerminatedHeader => write!(f, "Missing mobile terminated header"),
+            SbdError::MissingMobileTerminatedPayload => write!(f, "Missing mobile terminated payload"),
             SbdError::Oversized => write!(f, "Oversized message"),
+            SbdError::OversizedMtPayload(length) => write!(f, "MT payload of {} bytes exceeds the 1890-byte maximum", length),
             SbdError::Pattern(ref err) => write!(f, "Glob pattern error: {}", err),
             SbdError::Undersized(size) => write!(f, "Undersized message: {}", size),
         }
@@ -93,10 +133,21 @@ impl Error for SbdError {
             SbdError::Io(ref err) => err.description(),
             SbdError::InvalidImei => "invalid IMEI number",
             SbdError::InvalidProtocolRevisionNumber(_) => "invalid protocol revision number",
+            SbdError::InvalidMtHeaderLength(_) => "invalid mobile terminated header length",
+            SbdError::InvalidMtConfirmationLength(_) => "invalid mobile terminated confirmation message length",
+            SbdError::InvalidLocationLength(_) => "invalid lat/lon location information length",
+            SbdError::InvalidMoHeaderLength(_) => "invalid mobile originated header length",
+            SbdError::InvalidMessageLength(_) => "declared message length did not match the information elements present",
+            SbdError::UnknownMessageDirection(_) => "leading information element is neither an MO nor an MT header",
+            SbdError::MtDeliveryFailed(_) => "gateway rejected MT message",
+            SbdError::MissingMtConfirmationMessage => "gateway response did not include a confirmation message",
             SbdError::Glob(_) => "glob error",
             SbdError::MissingMobileOriginatedHeader => "missing mobile originated header",
             SbdError::MissingMobileOriginatedPayload => "missing mobile originated payload",
+            SbdError::MissingMobileTerminatedHeader => "missing mobile terminated header",
+            SbdError::MissingMobileTerminatedPayload => "missing mobile terminated payload",
             SbdError::Oversized => "oversized message",
+            SbdError::OversizedMtPayload(_) => "MT payload exceeds the 1890-byte maximum",
             SbdError::Pattern(_) => "glob pattern error",
             SbdError::Undersized(_) => "undersized message",
         }
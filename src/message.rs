@@ -0,0 +1,347 @@
+//! A direction-aware view over SBD messages.
+//!
+//! Mobile-originated and mobile-terminated messages share the same outer
+//! framing (protocol revision, overall length, information elements) and
+//! differ only in which header IEI (`0x01` vs `0x41`) leads the element
+//! sequence. `Message` sniffs that leading IEI and decodes into whichever
+//! direction it finds, so callers that don't know in advance which kind of
+//! stream they're reading can still handle both through one API.
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{mo, mt, Result, SbdError};
+
+/// Which direction a message travels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Sent *from* an Iridium modem.
+    MobileOriginated,
+    /// Sent *to* an Iridium modem.
+    MobileTerminated,
+}
+
+/// A GPS fix the Iridium network attached to a message.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location {
+    /// Decimal degrees, positive north.
+    pub latitude: f64,
+    /// Decimal degrees, positive east.
+    pub longitude: f64,
+    /// The radius, in km, within which the fix is expected to lie.
+    pub cep_radius_km: u32,
+}
+
+impl From<crate::information_element::Location> for Location {
+    fn from(location: crate::information_element::Location) -> Location {
+        Location {
+            latitude: location.latitude,
+            longitude: location.longitude,
+            cep_radius_km: location.cep_radius_km,
+        }
+    }
+}
+
+/// Whether a submitted MT message was queued, sent with no queue, or rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmationStatus {
+    /// The message was accepted and is at this position in the MT queue.
+    Queued(u16),
+    /// The message was sent successfully with no queue.
+    NoQueue,
+    /// The gateway rejected the message.
+    Error(ConfirmationError),
+}
+
+impl From<mt::ConfirmationStatus> for ConfirmationStatus {
+    fn from(status: mt::ConfirmationStatus) -> ConfirmationStatus {
+        match status {
+            mt::ConfirmationStatus::Queued(position) => ConfirmationStatus::Queued(position),
+            mt::ConfirmationStatus::NoQueue => ConfirmationStatus::NoQueue,
+            mt::ConfirmationStatus::Error(error) => ConfirmationStatus::Error(error.into()),
+        }
+    }
+}
+
+/// A named MT message status error condition, decoded from a negative status value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmationError {
+    /// The IMEI in the MT header is invalid or not provisioned.
+    InvalidImei,
+    /// The MT payload exceeds the maximum allowed size.
+    PayloadTooLarge,
+    /// There is no MO header associated with this message.
+    NoAssociatedMoHeader,
+    /// A status value not recognized by this crate.
+    Unknown(i16),
+}
+
+impl From<mt::ConfirmationError> for ConfirmationError {
+    fn from(error: mt::ConfirmationError) -> ConfirmationError {
+        match error {
+            mt::ConfirmationError::InvalidImei => ConfirmationError::InvalidImei,
+            mt::ConfirmationError::PayloadTooLarge => ConfirmationError::PayloadTooLarge,
+            mt::ConfirmationError::NoAssociatedMoHeader => ConfirmationError::NoAssociatedMoHeader,
+            mt::ConfirmationError::Unknown(status) => ConfirmationError::Unknown(status),
+        }
+    }
+}
+
+/// The gateway's Confirmation Message for a submitted MT message.
+#[derive(Debug, Clone, Copy)]
+pub struct Confirmation {
+    auto_id_reference: u32,
+    status: ConfirmationStatus,
+}
+
+impl Confirmation {
+    /// The Auto ID Reference assigned to this message by the gateway.
+    pub fn auto_id_reference(&self) -> u32 {
+        self.auto_id_reference
+    }
+
+    /// Whether the message was queued, sent with no queue, or rejected.
+    pub fn status(&self) -> ConfirmationStatus {
+        self.status
+    }
+}
+
+impl From<mt::Confirmation> for Confirmation {
+    fn from(confirmation: mt::Confirmation) -> Confirmation {
+        Confirmation {
+            auto_id_reference: confirmation.auto_id_reference(),
+            status: confirmation.status().into(),
+        }
+    }
+}
+
+/// A mobile originated message, decoded down to its publicly useful fields.
+#[derive(Debug, Clone, Default)]
+pub struct MoMessage {
+    imei: Option<String>,
+    payload: Option<Vec<u8>>,
+    location: Option<Location>,
+}
+
+impl MoMessage {
+    /// The IMEI that originated this message, if its header was present.
+    pub fn imei(&self) -> Option<&str> {
+        self.imei.as_deref()
+    }
+
+    /// This message's payload bytes, if one was attached.
+    pub fn payload(&self) -> Option<&[u8]> {
+        self.payload.as_deref()
+    }
+
+    /// The GPS location the Iridium network attached to this message, if any.
+    pub fn location(&self) -> Option<Location> {
+        self.location
+    }
+}
+
+impl From<mo::Message> for MoMessage {
+    fn from(message: mo::Message) -> MoMessage {
+        MoMessage {
+            imei: message.imei().map(str::to_string),
+            payload: message.payload_bytes().map(<[u8]>::to_vec),
+            location: message.location().map(Location::from),
+        }
+    }
+}
+
+/// A mobile terminated message, decoded down to its publicly useful fields.
+#[derive(Debug, Clone, Default)]
+pub struct MtMessage {
+    imei: Option<String>,
+    payload: Option<Vec<u8>>,
+    confirmation: Option<Confirmation>,
+    location: Option<Location>,
+}
+
+impl MtMessage {
+    /// The IMEI this message was addressed to, if its header was present.
+    pub fn imei(&self) -> Option<&str> {
+        self.imei.as_deref()
+    }
+
+    /// This message's payload bytes, if one was attached.
+    pub fn payload(&self) -> Option<&[u8]> {
+        self.payload.as_deref()
+    }
+
+    /// The gateway's delivery confirmation, if this message carried one.
+    pub fn confirmation(&self) -> Option<Confirmation> {
+        self.confirmation
+    }
+
+    /// The GPS location the Iridium network attached to this message, if any.
+    pub fn location(&self) -> Option<Location> {
+        self.location
+    }
+}
+
+impl From<mt::Message> for MtMessage {
+    fn from(mut message: mt::Message) -> MtMessage {
+        let imei = message.imei().map(str::to_string);
+        let payload = message.payload_bytes().map(<[u8]>::to_vec);
+        let location = message.location().map(Location::from);
+        MtMessage {
+            imei,
+            payload,
+            location,
+            confirmation: message.confirmation.take().map(Confirmation::from),
+        }
+    }
+}
+
+/// A parsed SBD message, either mobile originated or mobile terminated.
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// A message sent *from* an Iridium modem.
+    MobileOriginated(MoMessage),
+    /// A message sent *to* an Iridium modem.
+    MobileTerminated(MtMessage),
+}
+
+impl Message {
+    /// Reads a `Message` from a stream, sniffing the leading header IEI to decide
+    /// whether it is mobile originated or mobile terminated.
+    pub fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Message> {
+        let revision = rdr.read_u8()?;
+        if revision != 1 {
+            return Err(SbdError::InvalidProtocolRevisionNumber(revision));
+        }
+        let length = rdr.read_u16::<BigEndian>()?;
+        let mut body = vec![0u8; length as usize];
+        rdr.read_exact(&mut body)?;
+        let iei = *body.first().ok_or(SbdError::Undersized(0))?;
+
+        // Re-assemble the frame so the direction-specific parser, which expects
+        // to read the protocol revision and length itself, can take over.
+        let mut frame = Vec::with_capacity(3 + body.len());
+        frame.write_u8(revision)?;
+        frame.write_u16::<BigEndian>(length)?;
+        frame.extend_from_slice(&body);
+
+        match iei {
+            0x01 => Ok(Message::MobileOriginated(
+                mo::Message::read_from(&mut &frame[..])?.into(),
+            )),
+            0x41 => Ok(Message::MobileTerminated(
+                mt::Message::read_from(&mut &frame[..])?.into(),
+            )),
+            other => Err(SbdError::UnknownMessageDirection(other)),
+        }
+    }
+
+    /// Which direction this message travels.
+    pub fn direction(&self) -> Direction {
+        match self {
+            Message::MobileOriginated(_) => Direction::MobileOriginated,
+            Message::MobileTerminated(_) => Direction::MobileTerminated,
+        }
+    }
+
+    /// This message as a mobile terminated message, if that's its direction.
+    pub fn as_mt(&self) -> Option<&MtMessage> {
+        match self {
+            Message::MobileTerminated(message) => Some(message),
+            Message::MobileOriginated(_) => None,
+        }
+    }
+
+    /// This message as a mobile originated message, if that's its direction.
+    pub fn as_mo(&self) -> Option<&MoMessage> {
+        match self {
+            Message::MobileOriginated(message) => Some(message),
+            Message::MobileTerminated(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_message {
+    use super::{Direction, Message};
+
+    fn mt_frame() -> Vec<u8> {
+        // Protocol revision 1, an MT header (IEI 0x41, length 21) with a zeroed
+        // client message id, IMEI, and disposition flags.
+        let mut header = vec![0x41, 0x00, 0x15];
+        header.extend_from_slice(&[0u8; 21]);
+        let mut msg = vec![1, 0x00, header.len() as u8];
+        msg.extend(header);
+        msg
+    }
+
+    fn mo_frame() -> Vec<u8> {
+        // Protocol revision 1, an MO header (IEI 0x01, length 28) with zeroed fields.
+        let mut header = vec![0x01, 0x00, 0x1c];
+        header.extend_from_slice(&[0u8; 28]);
+        let mut msg = vec![1, 0x00, header.len() as u8];
+        msg.extend(header);
+        msg
+    }
+
+    #[test]
+    fn reads_mobile_terminated() {
+        let msg = mt_frame();
+        let message = Message::read_from(&mut &msg[..]).unwrap();
+        assert_eq!(message.direction(), Direction::MobileTerminated);
+        assert_eq!(message.as_mt().unwrap().imei(), Some("\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0"));
+        assert!(message.as_mo().is_none());
+    }
+
+    #[test]
+    fn reads_mobile_originated() {
+        let msg = mo_frame();
+        let message = Message::read_from(&mut &msg[..]).unwrap();
+        assert_eq!(message.direction(), Direction::MobileOriginated);
+        assert!(message.as_mo().is_some());
+        assert!(message.as_mt().is_none());
+    }
+
+    #[test]
+    fn unknown_direction() {
+        let msg = vec![1, 0x00, 0x03, 0x45, 0x00, 0x00];
+        assert!(Message::read_from(&mut &msg[..]).is_err());
+    }
+
+    #[test]
+    fn reads_mobile_terminated_confirmation_and_location() {
+        use super::ConfirmationStatus;
+
+        let mut msg = mt_frame();
+
+        // MT Confirmation Message (IEI 0x44): client msg id, IMEI, auto id
+        // reference 7, status 1 (queued at position 1).
+        let mut confirmation = vec![0x44, 0x00, 0x19];
+        confirmation.extend_from_slice(&[0u8; 4]);
+        confirmation.extend_from_slice(&[0u8; 15]);
+        confirmation.extend_from_slice(&7u32.to_be_bytes());
+        confirmation.extend_from_slice(&1i16.to_be_bytes());
+
+        // MT Lat/Lon Location Information (IEI 0x43): north, east, 10 degrees
+        // latitude, 20 degrees longitude, 5 km CEP radius.
+        let mut location = vec![0x43, 0x00, 0x0b];
+        location.push(0); // flags: north and east
+        location.push(10); // latitude degrees
+        location.extend_from_slice(&0u16.to_be_bytes()); // latitude thousandths
+        location.push(20); // longitude degrees
+        location.extend_from_slice(&0u16.to_be_bytes()); // longitude thousandths
+        location.extend_from_slice(&5u32.to_be_bytes()); // CEP radius
+
+        let length = u16::try_from(msg.len() - 3 + confirmation.len() + location.len()).unwrap();
+        msg[1..3].copy_from_slice(&length.to_be_bytes());
+        msg.extend(confirmation);
+        msg.extend(location);
+
+        let message = Message::read_from(&mut &msg[..]).unwrap();
+        let mt = message.as_mt().unwrap();
+        assert_eq!(mt.confirmation().unwrap().auto_id_reference(), 7);
+        assert_eq!(mt.confirmation().unwrap().status(), ConfirmationStatus::Queued(1));
+        let location = mt.location().unwrap();
+        assert!((location.latitude - 10.0).abs() < 1e-3);
+        assert!((location.longitude - 20.0).abs() < 1e-3);
+        assert_eq!(location.cep_radius_km, 5);
+    }
+}
@@ -0,0 +1,319 @@
+//! Mobile Originated
+//!
+//! Messages sent *from* an Iridium modem, either to an email address via MIME
+//! attachment or directly to a DirectIP host. This module only concerns itself
+//! with the wire format, not with how a particular transport delivers the bytes.
+
+/*
+Protocol Revision Number        1   1
+Overall Message Length          2   variable
+MO Header IEI                   1   0x01
+MO Header Length                2   28
+CDR Reference                   4
+IMEI (User ID)                  15  300034010123450
+Session Status                  1
+MOMSN                           2
+MTMSN                           2
+Time of Session                 4
+MO Payload IEI                  1   0x02
+MO Payload Length                2   variable
+MO Payload                      variable  Payload Bytes
+*/
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::header::SbdHeader;
+use crate::information_element::{Location, SbdPayload};
+use crate::{Result, SbdError};
+
+/// Mobile Originated Header
+#[derive(Debug)]
+pub(crate) struct Header {
+    cdr_reference: u32,
+    imei: [u8; 15],
+    session_status: u8,
+    momsn: u16,
+    mtmsn: u16,
+    time_of_session: u32,
+}
+
+impl Header {
+    fn len(&self) -> usize {
+        28
+    }
+
+    fn write<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize> {
+        wtr.write_u8(0x01)?;
+        wtr.write_u16::<BigEndian>(28)?;
+        wtr.write_u32::<BigEndian>(self.cdr_reference)?;
+        wtr.write_all(&self.imei)?;
+        wtr.write_u8(self.session_status)?;
+        wtr.write_u16::<BigEndian>(self.momsn)?;
+        wtr.write_u16::<BigEndian>(self.mtmsn)?;
+        wtr.write_u32::<BigEndian>(self.time_of_session)?;
+        Ok(31)
+    }
+
+    /// Reads a `Header` from a stream, assuming the IEI byte has already been consumed.
+    fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Header> {
+        let length = rdr.read_u16::<BigEndian>()?;
+        if length != 28 {
+            return Err(SbdError::InvalidMoHeaderLength(length));
+        }
+        let cdr_reference = rdr.read_u32::<BigEndian>()?;
+        let mut imei = [0u8; 15];
+        rdr.read_exact(&mut imei)?;
+        if !imei.is_ascii() {
+            return Err(SbdError::InvalidImei);
+        }
+        let session_status = rdr.read_u8()?;
+        let momsn = rdr.read_u16::<BigEndian>()?;
+        let mtmsn = rdr.read_u16::<BigEndian>()?;
+        let time_of_session = rdr.read_u32::<BigEndian>()?;
+        Ok(Header {
+            cdr_reference,
+            imei,
+            session_status,
+            momsn,
+            mtmsn,
+            time_of_session,
+        })
+    }
+}
+
+impl SbdHeader for Header {
+    fn write_to<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize> {
+        self.write(wtr)
+    }
+
+    fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Header> {
+        Header::read_from(rdr)
+    }
+
+    fn imei(&self) -> &str {
+        // `read_from` already rejected non-ASCII IMEI bytes, so this is infallible
+        // for any header that reached us through the normal decode path.
+        std::str::from_utf8(&self.imei).expect("IMEI is always ASCII")
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod test_mo_header {
+    use super::Header;
+    use crate::header::SbdHeader;
+
+    #[test]
+    fn write_then_read_from() {
+        let header = Header {
+            cdr_reference: 1,
+            imei: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
+            session_status: 0,
+            momsn: 2,
+            mtmsn: 3,
+            time_of_session: 1_600_000_000,
+        };
+        let mut msg = vec![];
+        let n = header.write(&mut msg).unwrap();
+        assert_eq!(n, 31);
+        // Skip the IEI byte, as the dispatcher in `InformationElement::read_with_iei` would.
+        let read_header = Header::read_from(&mut &msg[1..]).unwrap();
+        assert_eq!(read_header.cdr_reference, 1);
+        assert_eq!(read_header.imei, header.imei);
+        assert_eq!(read_header.session_status, 0);
+        assert_eq!(read_header.momsn, 2);
+        assert_eq!(read_header.mtmsn, 3);
+        assert_eq!(read_header.time_of_session, 1_600_000_000);
+        assert_eq!(SbdHeader::imei(&read_header).as_bytes(), &header.imei);
+    }
+
+    #[test]
+    fn header_read_from_bad_length() {
+        let msg = [0x00, 0x1d, 0, 0, 0, 0];
+        assert!(Header::read_from(&mut &msg[..]).is_err());
+    }
+
+    #[test]
+    fn header_read_from_non_ascii_imei() {
+        let mut msg = vec![0x00, 0x1c, 0, 0, 0, 0];
+        msg.extend_from_slice(&[0xffu8; 15]);
+        msg.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(Header::read_from(&mut &msg[..]).is_err());
+    }
+}
+
+/// Mobile Originated Payload
+#[derive(Debug)]
+pub(crate) struct Payload {
+    payload: Vec<u8>,
+}
+
+impl Payload {
+    fn len(&self) -> usize {
+        self.payload.len()
+    }
+
+    fn write<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize> {
+        wtr.write_u8(0x02)?;
+        let n = self.payload.len();
+        wtr.write_u16::<BigEndian>(
+            n.try_into()
+                .expect("MO Payload's length was supposed to be u16"),
+        )?;
+        wtr.write_all(&self.payload)?;
+        Ok(3 + n)
+    }
+
+    /// Reads a `Payload` from a stream, assuming the IEI byte has already been consumed.
+    fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Payload> {
+        let length = rdr.read_u16::<BigEndian>()?;
+        let mut payload = vec![0u8; length as usize];
+        rdr.read_exact(&mut payload)?;
+        Ok(Payload { payload })
+    }
+
+    /// This payload's raw bytes.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+impl SbdPayload for Payload {
+    fn write_to<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize> {
+        self.write(wtr)
+    }
+
+    fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Payload> {
+        Payload::read_from(rdr)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod test_payload {
+    use super::Payload;
+
+    #[test]
+    fn write_then_read_from() {
+        let payload = Payload {
+            payload: vec![1, 2, 3, 4, 5],
+        };
+        let mut msg = vec![];
+        payload.write(&mut msg).unwrap();
+        // Skip the IEI byte, as the dispatcher in `InformationElement::read_with_iei` would.
+        let read_payload = Payload::read_from(&mut &msg[1..]).unwrap();
+        assert_eq!(read_payload.payload, payload.payload);
+    }
+}
+
+/// An MO information element: a typed header, payload, or GPS location, or an
+/// unrecognized element preserved verbatim. See `crate::information_element`.
+type InformationElement = crate::information_element::InformationElement<Header, Payload>;
+
+/// A parsed Mobile Originated message.
+#[derive(Debug, Default)]
+pub(crate) struct Message {
+    header: Option<Header>,
+    payload: Option<Payload>,
+    location: Option<Location>,
+    /// Unrecognized information elements, preserved for round-tripping.
+    unknown: Vec<InformationElement>,
+}
+
+impl Message {
+    pub(crate) fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Message> {
+        let revision = rdr.read_u8()?;
+        if revision != 1 {
+            return Err(SbdError::InvalidProtocolRevisionNumber(revision));
+        }
+        let length = rdr.read_u16::<BigEndian>()? as usize;
+        let mut consumed = 0;
+        let mut message = Message::default();
+        while consumed < length {
+            let iei = rdr.read_u8()?;
+            let element = InformationElement::read_with_iei(iei, rdr, 0x01, 0x02, 0x03)?;
+            consumed += 3 + element.len();
+            if consumed > length {
+                return Err(SbdError::InvalidMessageLength(length));
+            }
+            match element {
+                InformationElement::H(header) => message.header = Some(header),
+                InformationElement::P(payload) => message.payload = Some(payload),
+                InformationElement::L(location) => message.location = Some(location),
+                element @ InformationElement::Unknown { .. } => message.unknown.push(element),
+            }
+        }
+        Ok(message)
+    }
+
+    /// The IMEI that originated this message, if the header was present.
+    pub(crate) fn imei(&self) -> Option<&str> {
+        self.header.as_ref().map(SbdHeader::imei)
+    }
+
+    /// This message's payload bytes, if one was attached.
+    pub(crate) fn payload_bytes(&self) -> Option<&[u8]> {
+        self.payload.as_ref().map(Payload::as_bytes)
+    }
+
+    /// The GPS location the Iridium network attached to this message, if any.
+    pub(crate) fn location(&self) -> Option<Location> {
+        self.location
+    }
+}
+
+#[cfg(test)]
+mod test_message {
+    use super::{Header, Message, Payload};
+
+    #[test]
+    fn read_from_header_and_payload() {
+        let header = Header {
+            cdr_reference: 1,
+            imei: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
+            session_status: 0,
+            momsn: 2,
+            mtmsn: 0,
+            time_of_session: 1_600_000_000,
+        };
+        let payload = Payload {
+            payload: vec![1, 2, 3],
+        };
+        let mut msg = vec![1]; // protocol revision
+        let mut body = vec![];
+        header.write(&mut body).unwrap();
+        payload.write(&mut body).unwrap();
+        msg.extend(u16::try_from(body.len()).unwrap().to_be_bytes().iter());
+        msg.extend(body);
+
+        let message = Message::read_from(&mut &msg[..]).unwrap();
+        assert_eq!(message.imei().unwrap().as_bytes(), &header.imei);
+        assert_eq!(message.payload.as_ref().unwrap().payload, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn read_from_declared_length_too_short() {
+        let header = Header {
+            cdr_reference: 1,
+            imei: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
+            session_status: 0,
+            momsn: 2,
+            mtmsn: 0,
+            time_of_session: 1_600_000_000,
+        };
+        let mut msg = vec![1]; // protocol revision
+        let mut body = vec![];
+        header.write(&mut body).unwrap();
+        // Declare a length shorter than the header element actually written.
+        msg.extend((body.len() as u16 - 1).to_be_bytes().iter());
+        msg.extend(body);
+
+        assert!(Message::read_from(&mut &msg[..]).is_err());
+    }
+}
@@ -28,23 +28,34 @@ MT Payload Length               2   70
 MT Payload                      70  Payload Bytes
 */
 
-use byteorder::{BigEndian, WriteBytesExt};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 
-use crate::Error;
+use crate::information_element::{Location, SbdPayload};
+use crate::{Result, SbdError};
 
-#[derive(Debug)]
+/// Parses an ASCII IMEI string into the crate's fixed-width wire representation.
+pub(crate) fn imei_from_str(imei: &str) -> Result<[u8; 15]> {
+    if imei.len() != 15 || !imei.is_ascii() {
+        return Err(SbdError::InvalidImei);
+    }
+    let mut bytes = [0u8; 15];
+    bytes.copy_from_slice(imei.as_bytes());
+    Ok(bytes)
+}
+
+#[derive(Debug, Default, Clone, Copy)]
 /// Disposition Flags
 ///
 /// Note: byte 3 was not defined at this point, skipping to 3rd.
 /// Therefore, all flags on is 0b0000_0000_0011_1011
 ///
 /// Table 5-9
-struct DispositionFlags {
-    flush_queue: bool,
-    send_ring_alert: bool,
-    update_location: bool,
-    high_priority: bool,
-    assign_mtmsn: bool,
+pub(crate) struct DispositionFlags {
+    pub(crate) flush_queue: bool,
+    pub(crate) send_ring_alert: bool,
+    pub(crate) update_location: bool,
+    pub(crate) high_priority: bool,
+    pub(crate) assign_mtmsn: bool,
 }
 
 impl DispositionFlags {
@@ -56,7 +67,17 @@ impl DispositionFlags {
             + u16::from(self.flush_queue)
     }
 
-    fn write<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize, Error> {
+    fn decode(flags: u16) -> DispositionFlags {
+        DispositionFlags {
+            flush_queue: flags & 0b1 != 0,
+            send_ring_alert: flags & 0b10 != 0,
+            update_location: flags & 0b1000 != 0,
+            high_priority: flags & 0b1_0000 != 0,
+            assign_mtmsn: flags & 0b10_0000 != 0,
+        }
+    }
+
+    fn write<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize> {
         wtr.write_u16::<BigEndian>(self.encode())?;
         Ok(2)
     }
@@ -130,11 +151,31 @@ mod test_disposition_flags {
 
         assert_eq!(flags.encode(), 59);
     }
+
+    #[test]
+    fn decode_all_true() {
+        let flags = DispositionFlags::decode(59);
+        assert!(flags.flush_queue);
+        assert!(flags.send_ring_alert);
+        assert!(flags.update_location);
+        assert!(flags.high_priority);
+        assert!(flags.assign_mtmsn);
+    }
+
+    #[test]
+    fn decode_all_false() {
+        let flags = DispositionFlags::decode(0);
+        assert!(!flags.flush_queue);
+        assert!(!flags.send_ring_alert);
+        assert!(!flags.update_location);
+        assert!(!flags.high_priority);
+        assert!(!flags.assign_mtmsn);
+    }
 }
 
 /// Mobile Terminated Header
 #[derive(Debug)]
-struct Header {
+pub(crate) struct Header {
     // IEI: 0x41 [1] (Table 5-1)
     // Header length [2]
     client_msg_id: u32, // or 4 u8?
@@ -143,11 +184,19 @@ struct Header {
 }
 
 impl Header {
+    pub(crate) fn new(client_msg_id: u32, imei: [u8; 15], disposition_flags: DispositionFlags) -> Header {
+        Header {
+            client_msg_id,
+            imei,
+            disposition_flags: disposition_flags.encode(),
+        }
+    }
+
     fn len(&self) -> usize {
         21
     }
 
-    fn write<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize, Error> {
+    pub(crate) fn write<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize> {
         wtr.write_u8(0x41)?;
         wtr.write_u16::<BigEndian>(21)?;
         wtr.write_u32::<BigEndian>(self.client_msg_id)?;
@@ -163,6 +212,51 @@ impl Header {
             .expect("Failed to write MT-Header to a vec.");
         buffer
     }
+
+    /// Reads a `Header` from a stream, assuming the IEI byte has already been consumed.
+    pub(crate) fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Header> {
+        let length = rdr.read_u16::<BigEndian>()?;
+        if length != 21 {
+            return Err(SbdError::InvalidMtHeaderLength(length));
+        }
+        let client_msg_id = rdr.read_u32::<BigEndian>()?;
+        let mut imei = [0u8; 15];
+        rdr.read_exact(&mut imei)?;
+        if !imei.is_ascii() {
+            return Err(SbdError::InvalidImei);
+        }
+        let disposition_flags = rdr.read_u16::<BigEndian>()?;
+        Ok(Header {
+            client_msg_id,
+            imei,
+            disposition_flags,
+        })
+    }
+
+    /// Decodes this header's raw disposition flags into a `DispositionFlags` struct.
+    fn disposition_flags(&self) -> DispositionFlags {
+        DispositionFlags::decode(self.disposition_flags)
+    }
+}
+
+impl crate::header::SbdHeader for Header {
+    fn write_to<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize> {
+        self.write(wtr)
+    }
+
+    fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Header> {
+        Header::read_from(rdr)
+    }
+
+    fn imei(&self) -> &str {
+        // `read_from` already rejected non-ASCII IMEI bytes, so this is infallible
+        // for any header that reached us through the normal decode path.
+        std::str::from_utf8(&self.imei).expect("IMEI is always ASCII")
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
 }
 
 #[cfg(test)]
@@ -206,22 +300,60 @@ mod test_mt_header {
             ]
         );
     }
+
+    #[test]
+    fn header_read_from() {
+        let header = Header {
+            client_msg_id: 9999,
+            imei: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
+            disposition_flags: 59,
+        };
+        let mut msg = vec![];
+        header.write(&mut msg).unwrap();
+        // Skip the IEI byte, as the dispatcher in `InformationElement::read_from` would.
+        let read_header = Header::read_from(&mut &msg[1..]).unwrap();
+        assert_eq!(read_header.client_msg_id, 9999);
+        assert_eq!(read_header.imei, header.imei);
+        assert_eq!(read_header.disposition_flags, 59);
+        assert!(read_header.disposition_flags().assign_mtmsn);
+    }
+
+    #[test]
+    fn header_read_from_bad_length() {
+        let msg = [0x00, 0x16, 0, 0, 0, 0];
+        assert!(Header::read_from(&mut &msg[..]).is_err());
+    }
+
+    #[test]
+    fn header_read_from_non_ascii_imei() {
+        let mut msg = vec![0x00, 0x15, 0, 0, 0, 0];
+        msg.extend_from_slice(&[0xffu8; 15]);
+        msg.extend_from_slice(&[0, 0]);
+        assert!(Header::read_from(&mut &msg[..]).is_err());
+    }
 }
 
 #[derive(Debug)]
 /// Mobile Terminated Payload
 ///
 /// Note that length is a 2-bytes and valid range is 1-1890
-struct Payload {
+pub(crate) struct Payload {
     payload: Vec<u8>,
 }
 
 impl Payload {
+    /// The largest payload the DirectIP wire format allows.
+    pub(crate) const MAX_LEN: usize = 1890;
+
+    pub(crate) fn new(payload: Vec<u8>) -> Payload {
+        Payload { payload }
+    }
+
     fn len(&self) -> usize {
         self.payload.len()
     }
 
-    fn write<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize, Error> {
+    pub(crate) fn write<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize> {
         wtr.write_u8(0x42)?;
         let n = self.payload.len();
         wtr.write_u16::<BigEndian>(
@@ -231,19 +363,382 @@ impl Payload {
         wtr.write(&self.payload)?;
         Ok(3 + n)
     }
+
+    /// Reads a `Payload` from a stream, assuming the IEI byte has already been consumed.
+    pub(crate) fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Payload> {
+        let length = rdr.read_u16::<BigEndian>()?;
+        let mut payload = vec![0u8; length as usize];
+        rdr.read_exact(&mut payload)?;
+        Ok(Payload { payload })
+    }
+
+    /// This payload's raw bytes.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+impl SbdPayload for Payload {
+    fn write_to<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize> {
+        self.write(wtr)
+    }
+
+    fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Payload> {
+        Payload::read_from(rdr)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
 }
 
+/// The position of a confirmed message in the gateway's MT queue, or the error
+/// condition that prevented delivery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConfirmationStatus {
+    /// The message was accepted and is at this position in the MT queue.
+    Queued(u16),
+    /// The message was sent successfully with no queue.
+    NoQueue,
+    /// The gateway rejected the message.
+    Error(ConfirmationError),
+}
+
+/// A named MT message status error condition, decoded from a negative status value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConfirmationError {
+    /// The IMEI in the MT header is invalid or not provisioned.
+    InvalidImei,
+    /// The MT payload exceeds the maximum allowed size.
+    PayloadTooLarge,
+    /// There is no MO header associated with this message.
+    NoAssociatedMoHeader,
+    /// A status value not recognized by this crate.
+    Unknown(i16),
+}
+
+/// Mobile Terminated Confirmation Message
+///
+/// Sent by the Iridium gateway in response to a submitted MT message, echoing
+/// the client message id and IMEI and reporting delivery status.
+#[derive(Debug)]
+pub(crate) struct Confirmation {
+    client_msg_id: u32,
+    imei: [u8; 15],
+    auto_id_reference: u32,
+    status: i16,
+}
+
+impl Confirmation {
+    /// The Auto ID Reference assigned to this message by the gateway.
+    pub(crate) fn auto_id_reference(&self) -> u32 {
+        self.auto_id_reference
+    }
+
+    /// The raw MT message status, as sent by the gateway.
+    pub(crate) fn raw_status(&self) -> i16 {
+        self.status
+    }
+
+    fn len(&self) -> usize {
+        25
+    }
+
+    fn write<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize> {
+        wtr.write_u8(0x44)?;
+        wtr.write_u16::<BigEndian>(25)?;
+        wtr.write_u32::<BigEndian>(self.client_msg_id)?;
+        wtr.write(&self.imei)?;
+        wtr.write_u32::<BigEndian>(self.auto_id_reference)?;
+        wtr.write_i16::<BigEndian>(self.status)?;
+        Ok(28)
+    }
+
+    /// Reads a `Confirmation` from a stream, assuming the IEI byte has already been consumed.
+    pub(crate) fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Confirmation> {
+        let length = rdr.read_u16::<BigEndian>()?;
+        if length != 25 {
+            return Err(SbdError::InvalidMtConfirmationLength(length));
+        }
+        let client_msg_id = rdr.read_u32::<BigEndian>()?;
+        let mut imei = [0u8; 15];
+        rdr.read_exact(&mut imei)?;
+        let auto_id_reference = rdr.read_u32::<BigEndian>()?;
+        let status = rdr.read_i16::<BigEndian>()?;
+        Ok(Confirmation {
+            client_msg_id,
+            imei,
+            auto_id_reference,
+            status,
+        })
+    }
+
+    /// Decodes the raw status field into a `ConfirmationStatus`.
+    pub(crate) fn status(&self) -> ConfirmationStatus {
+        match self.status {
+            s if s >= 1 => ConfirmationStatus::Queued(s as u16),
+            0 => ConfirmationStatus::NoQueue,
+            -1 => ConfirmationStatus::Error(ConfirmationError::InvalidImei),
+            -2 => ConfirmationStatus::Error(ConfirmationError::PayloadTooLarge),
+            -3 => ConfirmationStatus::Error(ConfirmationError::NoAssociatedMoHeader),
+            other => ConfirmationStatus::Error(ConfirmationError::Unknown(other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_confirmation {
+    use super::{Confirmation, ConfirmationError, ConfirmationStatus};
+
+    fn confirmation_with_status(status: i16) -> Confirmation {
+        Confirmation {
+            client_msg_id: 9999,
+            imei: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
+            auto_id_reference: 42,
+            status,
+        }
+    }
+
+    #[test]
+    fn write_then_read_from() {
+        let confirmation = confirmation_with_status(3);
+        let mut msg = vec![];
+        let n = confirmation.write(&mut msg).unwrap();
+        assert_eq!(n, 28);
+        // Skip the IEI byte, as the dispatcher in `InformationElement::read_from` would.
+        let read_confirmation = Confirmation::read_from(&mut &msg[1..]).unwrap();
+        assert_eq!(read_confirmation.client_msg_id, 9999);
+        assert_eq!(read_confirmation.imei, confirmation.imei);
+        assert_eq!(read_confirmation.auto_id_reference, 42);
+        assert_eq!(read_confirmation.status, 3);
+    }
+
+    #[test]
+    fn status_queued() {
+        assert_eq!(confirmation_with_status(3).status(), ConfirmationStatus::Queued(3));
+    }
+
+    #[test]
+    fn status_no_queue() {
+        assert_eq!(confirmation_with_status(0).status(), ConfirmationStatus::NoQueue);
+    }
+
+    #[test]
+    fn status_invalid_imei() {
+        assert_eq!(
+            confirmation_with_status(-1).status(),
+            ConfirmationStatus::Error(ConfirmationError::InvalidImei)
+        );
+    }
+
+    #[test]
+    fn status_unknown() {
+        assert_eq!(
+            confirmation_with_status(-99).status(),
+            ConfirmationStatus::Error(ConfirmationError::Unknown(-99))
+        );
+    }
+}
+
+/// The MT information elements shared with MO: a typed header, payload, or GPS
+/// location, or an unrecognized element preserved verbatim. See
+/// `crate::information_element`.
+type SharedInformationElement = crate::information_element::InformationElement<Header, Payload>;
+
+/// An MT information element, extending the elements shared with MO with the
+/// MT-specific Confirmation Message.
 #[derive(Debug)]
 enum InformationElement {
-    H(Header),
-    P(Payload),
+    Shared(SharedInformationElement),
+    C(Confirmation),
 }
 
 impl InformationElement {
-    fn write<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize, Error> {
+    fn len(&self) -> usize {
+        match self {
+            InformationElement::Shared(element) => element.len(),
+            InformationElement::C(element) => element.len(),
+        }
+    }
+
+    fn write<W: std::io::Write>(&self, wtr: &mut W) -> Result<usize> {
         match self {
-            InformationElement::H(element) => element.write(wtr),
-            InformationElement::P(element) => element.write(wtr),
+            InformationElement::Shared(element) => element.write(wtr, 0x43),
+            InformationElement::C(element) => element.write(wtr),
+        }
+    }
+
+    /// Reads the next information element from a stream, including its leading IEI byte.
+    ///
+    /// Unrecognized IEIs are not an error: their body is read generically and
+    /// preserved as `InformationElement::Shared(SharedInformationElement::Unknown)`.
+    fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<InformationElement> {
+        let iei = rdr.read_u8()?;
+        if iei == 0x44 {
+            Ok(InformationElement::C(Confirmation::read_from(rdr)?))
+        } else {
+            Ok(InformationElement::Shared(
+                SharedInformationElement::read_with_iei(iei, rdr, 0x41, 0x42, 0x43)?,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_information_element {
+    use super::{InformationElement, SharedInformationElement};
+
+    #[test]
+    fn unknown_write_then_read_from() {
+        let element = InformationElement::Shared(SharedInformationElement::Unknown {
+            iei: 0x45,
+            bytes: vec![1, 2, 3],
+        });
+        let mut msg = vec![];
+        let n = element.write(&mut msg).unwrap();
+        assert_eq!(n, 6);
+        let read_element = InformationElement::read_from(&mut &msg[..]).unwrap();
+        match read_element {
+            InformationElement::Shared(SharedInformationElement::Unknown { iei, bytes }) => {
+                assert_eq!(iei, 0x45);
+                assert_eq!(bytes, vec![1, 2, 3]);
+            }
+            _ => panic!("expected an Unknown information element"),
         }
     }
 }
+
+#[cfg(test)]
+mod test_payload {
+    use super::Payload;
+
+    #[test]
+    fn write_then_read_from() {
+        let payload = Payload {
+            payload: vec![1, 2, 3, 4, 5],
+        };
+        let mut msg = vec![];
+        payload.write(&mut msg).unwrap();
+        // Skip the IEI byte, as the dispatcher in `InformationElement::read_from` would.
+        let read_payload = Payload::read_from(&mut &msg[1..]).unwrap();
+        assert_eq!(read_payload.payload, payload.payload);
+    }
+}
+
+/// A parsed Mobile Terminated message.
+///
+/// Table 5-1 describes the message as a protocol revision number, an overall
+/// message length, and a sequence of information elements.
+#[derive(Debug, Default)]
+pub(crate) struct Message {
+    header: Option<Header>,
+    payload: Option<Payload>,
+    pub(crate) confirmation: Option<Confirmation>,
+    location: Option<Location>,
+    /// Unrecognized information elements, preserved for round-tripping.
+    unknown: Vec<InformationElement>,
+}
+
+impl Message {
+    pub(crate) fn read_from<R: std::io::Read>(rdr: &mut R) -> Result<Message> {
+        let revision = rdr.read_u8()?;
+        if revision != 1 {
+            return Err(SbdError::InvalidProtocolRevisionNumber(revision));
+        }
+        let length = rdr.read_u16::<BigEndian>()? as usize;
+        let mut consumed = 0;
+        let mut message = Message::default();
+        while consumed < length {
+            let element = InformationElement::read_from(rdr)?;
+            consumed += 3 + element.len();
+            if consumed > length {
+                return Err(SbdError::InvalidMessageLength(length));
+            }
+            match element {
+                InformationElement::Shared(SharedInformationElement::H(header)) => {
+                    message.header = Some(header)
+                }
+                InformationElement::Shared(SharedInformationElement::P(payload)) => {
+                    message.payload = Some(payload)
+                }
+                InformationElement::Shared(SharedInformationElement::L(location)) => {
+                    message.location = Some(location)
+                }
+                element @ InformationElement::Shared(SharedInformationElement::Unknown {
+                    ..
+                }) => message.unknown.push(element),
+                InformationElement::C(confirmation) => message.confirmation = Some(confirmation),
+            }
+        }
+        Ok(message)
+    }
+
+    /// The IMEI addressed by this message's header, if the header was present.
+    pub(crate) fn imei(&self) -> Option<&str> {
+        self.header.as_ref().map(crate::header::SbdHeader::imei)
+    }
+
+    /// This message's payload bytes, if one was attached.
+    pub(crate) fn payload_bytes(&self) -> Option<&[u8]> {
+        self.payload.as_ref().map(Payload::as_bytes)
+    }
+
+    /// The GPS location the Iridium network attached to this message, if any.
+    pub(crate) fn location(&self) -> Option<Location> {
+        self.location
+    }
+}
+
+#[cfg(test)]
+mod test_message {
+    use super::{Header, Message, Payload};
+
+    #[test]
+    fn read_from_header_and_payload() {
+        let header = Header {
+            client_msg_id: 9999,
+            imei: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
+            disposition_flags: 0,
+        };
+        let payload = Payload {
+            payload: vec![1, 2, 3],
+        };
+        let mut msg = vec![1]; // protocol revision
+        let mut body = vec![];
+        header.write(&mut body).unwrap();
+        payload.write(&mut body).unwrap();
+        msg.extend(
+            u16::try_from(body.len())
+                .unwrap()
+                .to_be_bytes()
+                .iter(),
+        );
+        msg.extend(body);
+
+        let message = Message::read_from(&mut &msg[..]).unwrap();
+        assert!(message.header.is_some());
+        assert_eq!(message.payload.unwrap().payload, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn read_from_declared_length_shorter_than_element() {
+        use super::Confirmation;
+
+        let confirmation = Confirmation {
+            client_msg_id: 9999,
+            imei: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14],
+            auto_id_reference: 42,
+            status: 1,
+        };
+        let mut body = vec![];
+        confirmation.write(&mut body).unwrap();
+
+        // Declare an overall length far shorter than the confirmation element
+        // that actually follows, as a gateway might on a corrupted link.
+        let mut msg = vec![1]; // protocol revision
+        msg.extend(5u16.to_be_bytes().iter());
+        msg.extend(body);
+
+        assert!(Message::read_from(&mut &msg[..]).is_err());
+    }
+}